@@ -9,7 +9,7 @@ const QUICK_SORTED_ARRAY: &[u32] = &{
         }
         i += 1;
     }
-    const_quicksort!(@12, &mut data);
+    const_quicksort!(&mut data);
     data
 };
 
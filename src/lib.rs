@@ -67,6 +67,96 @@ pub const fn expect_push<T, const LEN: usize>(v: &mut ArrayVec<T, LEN>, element:
     forget(res);
 }
 
+/// `floor(log2(n))`, used to size the introsort depth budget in [`const_quicksort_adv!`].
+#[doc(hidden)]
+pub const fn floor_log2(mut n: usize) -> u32 {
+    let mut log = 0;
+    while n > 1 {
+        n /= 2;
+        log += 1;
+    }
+    log
+}
+
+/// Returns the index (one of `$i`, `$j`, `$k`) holding the median of `$data[$i]`, `$data[$j]` and
+/// `$data[$k]`, used for median-of-three/ninther pivot selection.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_median3_idx {
+    ($data:expr, $i:expr, $j:expr, $k:expr, $cmp:path) => {{
+        let (i, j, k) = ($i, $j, $k);
+        if {$cmp!($data[i], $data[j])} {
+            if {$cmp!($data[j], $data[k])} {
+                j
+            } else if {$cmp!($data[i], $data[k])} {
+                k
+            } else {
+                i
+            }
+        } else if {$cmp!($data[i], $data[k])} {
+            i
+        } else if {$cmp!($data[j], $data[k])} {
+            k
+        } else {
+            j
+        }
+    }};
+}
+
+/// Sifts `$data[$start]` down through the binary heap occupying `$data[..$end]`, used by the
+/// introsort depth-budget fallback in [`const_quicksort_adv!`] and by [`const_heapsort_adv!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_heap_sift_down {
+    ($data:expr, $start:expr, $end:expr, $cmp:path) => {{
+        let end = $end;
+        let mut root = $start;
+        loop {
+            let mut child = 2 * root + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && {$cmp!($data[child], $data[child + 1])} {
+                child += 1;
+            }
+            if {$cmp!($data[root], $data[child])} {
+                $data.swap(root, child);
+                root = child;
+            } else {
+                break;
+            }
+        }
+    }};
+}
+
+/// Sifts `$keys[$start]` down through the binary heap occupying `$keys[..$end]`, mirroring every
+/// swap onto `$data` so the two stay in lockstep. Used by the by-key sorts' introsort depth-budget
+/// fallback (e.g. [`const_quicksort_by_key_adv!`]), analogous to [`const_heap_sift_down!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_heap_sift_down_by_key {
+    ($keys:expr, $data:expr, $start:expr, $end:expr, $cmp:path) => {{
+        let end = $end;
+        let mut root = $start;
+        loop {
+            let mut child = 2 * root + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && {$cmp!($keys[child], $keys[child + 1])} {
+                child += 1;
+            }
+            if {$cmp!($keys[root], $keys[child])} {
+                $keys.swap(root, child);
+                $data.swap(root, child);
+                root = child;
+            } else {
+                break;
+            }
+        }
+    }};
+}
+
 /// Some nice gaps for shellsort
 #[doc(hidden)]
 pub const A366726: [usize; 32] = [
@@ -154,8 +244,14 @@ pub const A366726: [usize; 32] = [
 /// assert_eq!(FOOS_MUT_REF, [4, 2, 1, 3].map(Foo));
 /// ```
 ///
-/// The `@depth` parameter should only be used if you encounter a scenario where "stack overflows" start occuring.
-/// ```compile_fail
+/// Pivots are chosen as the median of three samples (a ninther — the median of three such
+/// medians — once the slice is larger than 128 elements), small subslices fall back to
+/// insertion sort, and each subslice carries a shrinking partition-depth budget that falls back
+/// to heapsort if it is exhausted. Together these keep the `ArrayVec` call stack at a safe
+/// `O(log n)` and avoid the O(n²) worst case that adversarial inputs (sorted, reverse-sorted,
+/// "organ pipe" data) trigger in a naive quicksort, so the `@depth` parameter is only needed to
+/// tune performance, never for correctness:
+/// ```
 /// use sort_const::const_quicksort;
 ///
 /// const SORTED_ARRAY: &[u32] = &{
@@ -167,7 +263,7 @@ pub const A366726: [usize; 32] = [
 ///         }
 ///         i += 1;
 ///     }
-///     const_quicksort!(@8, &mut data);
+///     const_quicksort!(&mut data);
 ///     data
 /// };
 /// ```
@@ -197,21 +293,37 @@ macro_rules! const_quicksort {
     }};
 }
 
-/// Sorts a `const` array or mutable slice as a `const` expression using quicksort.
+/// Sorts a `const` array or mutable slice as a `const` expression using introsort (quicksort
+/// with a median-of-three/ninther pivot, an insertion-sort base case, and a heapsort fallback
+/// once a subslice's partition-depth budget runs out).
 ///
-/// The optional `@` prefix parameter is the max stack size for recursion.
+/// The optional `@` prefix parameter is the max stack size for recursion; because the smaller of
+/// each pair of partitions is always pushed last (so it's processed first), and small subslices
+/// are insertion-sorted when popped instead of being split further, this only needs to hold
+/// `O(log n)` entries. Small subslices still take one stack entry each until they are popped.
 ///
 /// The first parameter is the data to be sorted.
 ///
 /// The second parameter is the path identifying the macro which should be invoked to compare elements `cmp!(a, b)`
 #[macro_export]
 macro_rules! const_quicksort_adv {
-    ($data:expr, $cmp:path) => { $crate::const_quicksort_adv!(@1024, $data, $cmp) };
+    ($data:expr, $cmp:path) => { $crate::const_quicksort_adv!(@64, $data, $cmp) };
     (@$depth:literal, $data:expr, $cmp:path) => {{
+        // Small subslices are sorted in place by insertion sort instead of being partitioned
+        // further; this avoids the overhead of quicksort on tiny inputs. They are still pushed
+        // onto the stack like any other subslice, but never push further entries of their own.
+        const INSERTION_SORT_THRESHOLD: usize = 16;
+
         let mut data = $crate::Wrapper($data);
-        let mut slices = $crate::ArrayVec::<&mut [_], $depth>::new();
-        $crate::expect_push(&mut slices, data.as_mut_slice());
-        while let Some(data) = slices.pop() {
+        let root = data.as_mut_slice();
+        // Each subslice we ever partition again spends one unit of this budget; hitting zero
+        // falls back to heapsort, which bounds the recursion to O(log n) partitions deep even for
+        // adversarial (sorted, reverse-sorted, "organ pipe") inputs.
+        let root_budget = 2 * $crate::floor_log2(root.len()) as usize;
+
+        let mut slices = $crate::ArrayVec::<(&mut [_], usize), $depth>::new();
+        $crate::expect_push(&mut slices, (root, root_budget));
+        while let Some((data, budget)) = slices.pop() {
             match data.len() {
                 0 | 1 => continue,
                 2 => {
@@ -220,9 +332,54 @@ macro_rules! const_quicksort_adv {
                     }
                     continue;
                 },
+                len if len <= INSERTION_SORT_THRESHOLD => {
+                    let mut i = 1;
+                    while i < len {
+                        let mut j = i;
+                        while j > 0 && {$cmp!(data[j], data[j - 1])} {
+                            data.swap(j, j - 1);
+                            j -= 1;
+                        }
+                        i += 1;
+                    }
+                    continue;
+                }
+                len if budget == 0 => {
+                    // Depth budget exhausted: fall back to heapsort, which is also the primitive
+                    // behind `const_heapsort_adv!`, to guarantee O(n log n) regardless of pivot
+                    // choices from here on.
+                    let mut i = len / 2;
+                    while i > 0 {
+                        i -= 1;
+                        $crate::const_heap_sift_down!(data, i, len, $cmp);
+                    }
+                    let mut end = len;
+                    while end > 1 {
+                        end -= 1;
+                        data.swap(0, end);
+                        $crate::const_heap_sift_down!(data, 0, end, $cmp);
+                    }
+                    continue;
+                }
                 _ => {}
             }
 
+            let len = data.len();
+            let last = len - 1;
+            let mid = len / 2;
+            let pivot_idx = if len > 128 {
+                // A ninther: the median of three medians sampled across the slice, which resists
+                // the patterns (sorted, reverse-sorted, "organ pipe") that defeat a plain
+                // median-of-three on larger inputs.
+                let third = len / 8;
+                let lo = $crate::const_median3_idx!(data, 0, third, 2 * third, $cmp);
+                let mi = $crate::const_median3_idx!(data, mid - third, mid, mid + third, $cmp);
+                let hi = $crate::const_median3_idx!(data, last - 2 * third, last - third, last, $cmp);
+                $crate::const_median3_idx!(data, lo, mi, hi, $cmp)
+            } else {
+                $crate::const_median3_idx!(data, 0, mid, last, $cmp)
+            };
+            data.swap(0, pivot_idx);
 
             let (pivot, rest) = data
                 .split_first_mut()
@@ -249,18 +406,19 @@ macro_rules! const_quicksort_adv {
             }
 
             data.swap(0, left);
+            let child_budget = budget - 1;
             let (left, right) = data.split_at_mut(left);
             match right.split_first_mut() {
                 None => {
-                    $crate::expect_push(&mut slices, left);
+                    $crate::expect_push(&mut slices, (left, child_budget));
                 }
                 Some((_pivot, right)) if left.len() >= right.len() => {
-                    $crate::expect_push(&mut slices, left);
-                    $crate::expect_push(&mut slices, right);
+                    $crate::expect_push(&mut slices, (left, child_budget));
+                    $crate::expect_push(&mut slices, (right, child_budget));
                 }
                 Some((_pivot, right)) => {
-                    $crate::expect_push(&mut slices, right);
-                    $crate::expect_push(&mut slices, left);
+                    $crate::expect_push(&mut slices, (right, child_budget));
+                    $crate::expect_push(&mut slices, (left, child_budget));
                 }
             }
         }
@@ -384,37 +542,1049 @@ macro_rules! const_shellsort_adv {
     }};
 }
 
-#[cfg(test)]
-mod test {
-    #[derive(Debug)]
-    struct Foo(u8);
+/// Sorts a `const` array or mutable slice as a `const` expression using merge sort.
+///
+/// Unlike [`const_quicksort`] and [`const_shellsort`], this sort is *stable*: elements that
+/// compare equal keep their original relative order, matching the default behaviour of
+/// `slice::sort`/`sort_by` in `core`/`alloc`.
+///
+/// The required `@` prefix parameter is the capacity of the scratch buffer used to merge each
+/// pair of adjacent runs; it must be at least as large as the data being sorted. Unlike
+/// `const_quicksort!`'s `@depth`, this has no safe size-independent default, so it is mandatory.
+///
+/// Can be called with just the data to be sorted, in which case elements are compared by `a < b` resulting in the smallest element at
+/// the head of the data and the largest at the tail.
+///
+/// ```
+/// use sort_const::const_mergesort;
+///
+/// const U8S: &[u8] = &const_mergesort!(@3, [3, 1, 2]);
+///
+/// assert_eq!(U8S, [1, 2, 3]);
+/// ```
+///
+/// Can also be called with a lambda-like expression (e.g. `|a, b| a < b`) where `true` identifies that `a` should come before `b`.
+///
+/// ```
+/// use sort_const::const_mergesort;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+///
+/// const FOOS_MUT_REF: &[Foo] = &{
+///     let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+///     const_mergesort!(@3, foo.split_last_mut().expect("failed").1, |a, b| a.0 > b.0);
+///     foo
+/// };
+///
+/// assert_eq!(FOOS_MUT_REF, [4, 2, 1, 3].map(Foo));
+/// ```
+///
+/// Can also be called with the name of a `const` function which must return a boolean and which will be evaluated over `&data[a], &data[b]`.
+///
+/// ```
+/// use sort_const::const_mergesort;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+///
+/// const fn gt(lhs: &Foo, rhs: &Foo) -> bool {
+///     lhs.0 > rhs.0
+/// }
+/// const FOOS_MUT_REF: &[Foo] = &{
+///     let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+///     const_mergesort!(@3, foo.split_last_mut().expect("failed").1, gt);
+///     foo
+/// };
+///
+/// assert_eq!(FOOS_MUT_REF, [4, 2, 1, 3].map(Foo));
+/// ```
+///
+/// Elements that compare equal are never reordered:
+///
+/// ```
+/// use sort_const::const_mergesort;
+///
+/// const PAIRS: &[(u8, u8)] = &const_mergesort!(@4, [(1, 0), (0, 0), (1, 1), (0, 1)], |a, b| a.0 < b.0);
+///
+/// assert_eq!(PAIRS, [(0, 0), (0, 1), (1, 0), (1, 1)]);
+/// ```
+#[macro_export]
+macro_rules! const_mergesort {
+    (@$cap:literal, $data:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_mergesort_adv!(@$cap, $data, cmp)
+    }};
+    (@$cap:literal, $data:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_mergesort_adv!(@$cap, $data, cmp)
+    }};
+    (@$cap:literal, $data:expr, $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $cmp(&$a, &$b) }};
+        }
+        $crate::const_mergesort_adv!(@$cap, $data, cmp)
+    }};
+}
 
-    const fn lt(a: &u8, b: &u8) -> bool {
-        *a < *b
-    }
+/// Sorts a `const` array or mutable slice as a `const` expression using merge sort.
+///
+/// Unlike `const_quicksort_adv!`'s `@depth` (a safe `O(log n)` default regardless of input size),
+/// the scratch buffer here must hold the entire input, so there is no size-independent default
+/// capacity to fall back to. The `@` prefix parameter is therefore required, not optional, and
+/// must be at least as large as the data being sorted.
+///
+/// The first parameter is the data to be sorted.
+///
+/// The second parameter is the path identifying the macro which should be invoked to compare elements `cmp!(a, b)`
+#[macro_export]
+macro_rules! const_mergesort_adv {
+    (@$cap:literal, $data:expr, $cmp:path) => {{
+        let mut data = $crate::Wrapper($data);
+        let arr = data.as_mut_slice();
+        let n = arr.len();
 
-    const U8S: &[u8] = &const_quicksort!([3, 2, 1]);
-    const U8S_FN: &[u8] = &const_quicksort!([3, 2, 1], lt);
-    const FOOS: &[Foo] = &const_quicksort!([Foo(1), Foo(2), Foo(4), Foo(3)], |a, b| a.0 > b.0);
-    const FOOS_MUT_REF: &[Foo] = &{
-        let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
-        const_quicksort!(foo.split_last_mut().expect("failed").1, |a, b| a.0 > b.0);
-        foo
-    };
+        // Bottom-up (iterative) merge sort: merge runs of size `width`, doubling `width` each
+        // pass, so no recursion stack is needed. Each merge moves elements through `merged` with
+        // `ptr::read`/`ptr::write` (rather than `Copy`/`Clone`) so non-`Copy` `const` types work
+        // exactly as they do in `const_shellsort!`'s hole-shifting.
+        let mut width = 1;
+        while width < n {
+            let mut lo = 0;
+            while lo < n {
+                let mid = if lo + width < n { lo + width } else { n };
+                let hi = if lo + 2 * width < n { lo + 2 * width } else { n };
 
-    #[test]
-    fn test_u8() {
-        assert_eq!(U8S, [1, 2, 3]);
-    }
+                if mid < hi {
+                    let mut merged = $crate::ArrayVec::<_, $cap>::new();
+                    let mut i = lo;
+                    let mut j = mid;
+                    while i < mid && j < hi {
+                        // Taking the left run whenever it isn't strictly greater than the right
+                        // one keeps equal elements in their original relative order.
+                        if !{$cmp!(arr[j], arr[i])} {
+                            $crate::expect_push(&mut merged, unsafe { ::core::ptr::read(&arr[i]) });
+                            i += 1;
+                        } else {
+                            $crate::expect_push(&mut merged, unsafe { ::core::ptr::read(&arr[j]) });
+                            j += 1;
+                        }
+                    }
+                    while i < mid {
+                        $crate::expect_push(&mut merged, unsafe { ::core::ptr::read(&arr[i]) });
+                        i += 1;
+                    }
+                    while j < hi {
+                        $crate::expect_push(&mut merged, unsafe { ::core::ptr::read(&arr[j]) });
+                        j += 1;
+                    }
 
-    #[test]
-    fn test_u8_fn() {
-        assert_eq!(U8S_FN, [1, 2, 3]);
-    }
+                    // `merged` holds the run in order but only supports LIFO access, so pop it
+                    // back from the tail of `[lo, hi)` towards `lo`.
+                    let mut k = hi;
+                    while let Some(value) = merged.pop() {
+                        k -= 1;
+                        unsafe { ::core::ptr::write(&mut arr[k], value); }
+                    }
+                    ::core::mem::forget(merged);
+                }
 
-    #[test]
-    fn test_foo() {
-        assert!(FOOS.iter().map(|v| v.0).eq([4, 3, 2, 1]));
-        assert!(FOOS_MUT_REF.iter().map(|v| v.0).eq([4, 2, 1, 3]));
+                lo += 2 * width;
+            }
+            width *= 2;
+        }
+
+        data.0
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using heap sort, entirely in
+/// place with no recursion stack and no scratch buffer (unlike [`const_quicksort`], which needs
+/// an `ArrayVec` call stack, or [`const_mergesort`], which needs a merge scratch buffer), giving
+/// a worst-case `O(n log n)` sort with guaranteed `O(1)` extra space.
+///
+/// Can be called with just the data to be sorted, in which case elements are compared by `a < b` resulting in the smallest element at
+/// the head of the data and the largest at the tail.
+///
+/// ```
+/// use sort_const::const_heapsort;
+///
+/// const U8S: &[u8] = &const_heapsort!([3, 1, 2]);
+///
+/// assert_eq!(U8S, [1, 2, 3]);
+/// ```
+///
+/// Can also be called with a lambda-like expression (e.g. `|a, b| a < b`) where `true` identifies that `a` should come before `b`.
+///
+/// ```
+/// use sort_const::const_heapsort;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+///
+/// const FOOS_MUT_REF: &[Foo] = &{
+///     let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+///     const_heapsort!(foo.split_last_mut().expect("failed").1, |a, b| a.0 > b.0);
+///     foo
+/// };
+///
+/// assert_eq!(FOOS_MUT_REF, [4, 2, 1, 3].map(Foo));
+/// ```
+///
+/// Can also be called with the name of a `const` function which must return a boolean and which will be evaluated over `&data[a], &data[b]`.
+///
+/// ```
+/// use sort_const::const_heapsort;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+///
+/// const fn gt(lhs: &Foo, rhs: &Foo) -> bool {
+///     lhs.0 > rhs.0
+/// }
+/// const FOOS_MUT_REF: &[Foo] = &{
+///     let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+///     const_heapsort!(foo.split_last_mut().expect("failed").1, gt);
+///     foo
+/// };
+///
+/// assert_eq!(FOOS_MUT_REF, [4, 2, 1, 3].map(Foo));
+/// ```
+#[macro_export]
+macro_rules! const_heapsort {
+    ($data:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_heapsort_adv!($data, cmp)
+    }};
+    ($data:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_heapsort_adv!($data, cmp)
+    }};
+    ($data:expr, $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $cmp(&$a, &$b) }};
+        }
+        $crate::const_heapsort_adv!($data, cmp)
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using heap sort.
+///
+/// The first parameter is the data to be sorted.
+///
+/// The second parameter is the path identifying the macro which should be invoked to compare elements `cmp!(a, b)`
+#[macro_export]
+macro_rules! const_heapsort_adv {
+    ($data:expr, $cmp:path) => {{
+        let mut data = $crate::Wrapper($data);
+        let arr = data.as_mut_slice();
+        let len = arr.len();
+
+        // Build a max-heap by sifting every non-leaf node down, starting from the last one.
+        let mut i = len / 2;
+        while i > 0 {
+            i -= 1;
+            $crate::const_heap_sift_down!(arr, i, len, $cmp);
+        }
+
+        // Repeatedly move the heap's max to the end of the still-unsorted prefix, then restore
+        // the heap property over what remains. This is the same sift-down primitive that
+        // `const_quicksort_adv!` falls back to once its partition-depth budget runs out.
+        let mut end = len;
+        while end > 1 {
+            end -= 1;
+            arr.swap(0, end);
+            $crate::const_heap_sift_down!(arr, 0, end, $cmp);
+        }
+
+        data.0
+    }};
+}
+
+/// Partitions a `const` array or mutable slice as a `const` expression so that `data[k]` holds
+/// the `k`-th smallest element (quickselect): every element before it compares `<= data[k]` and
+/// every element after it compares `>= data[k]`.
+///
+/// Unlike the full sorts in this crate, `const_select!` only ever descends into the one
+/// partition containing `k`, so it runs in expected `O(n)` and needs no stack buffer. Its pivot
+/// partition is 3-way (Dutch-flag), so a run of elements equal to the pivot resolves in a single
+/// pass instead of being re-split pivot-by-pivot, keeping duplicate-heavy data (ties,
+/// default/enum-like keys) from degrading towards `O(n^2)`.
+///
+/// Can be called with just the data and the target index `k`, in which case elements are compared by `a < b`.
+///
+/// ```
+/// use sort_const::const_select;
+///
+/// const DATA: &[u8] = &const_select!([5, 3, 1, 4, 2], 2);
+///
+/// assert_eq!(DATA[2], 3);
+/// ```
+///
+/// Can also be called with a lambda-like expression (e.g. `|a, b| a < b`) where `true` identifies that `a` should come before `b`.
+///
+/// ```
+/// use sort_const::const_select;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Foo(u8);
+///
+/// const FOOS_MUT_REF: &[Foo] = &{
+///     let mut foo = [Foo(5), Foo(3), Foo(1), Foo(4), Foo(2)];
+///     const_select!(foo.split_last_mut().expect("failed").1, 1, |a, b| a.0 > b.0);
+///     foo
+/// };
+///
+/// assert_eq!(FOOS_MUT_REF[1].0, 4);
+/// ```
+#[macro_export]
+macro_rules! const_select {
+    ($data:expr, $k:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_select_adv!($data, $k, cmp)
+    }};
+    ($data:expr, $k:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_select_adv!($data, $k, cmp)
+    }};
+    ($data:expr, $k:expr, $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $cmp(&$a, &$b) }};
+        }
+        $crate::const_select_adv!($data, $k, cmp)
+    }};
+}
+
+/// Partitions a `const` array or mutable slice as a `const` expression so that `data[k]` holds
+/// the `k`-th smallest element.
+///
+/// The first parameter is the data, the second is the target index `k`, the third is the path
+/// identifying the macro which should be invoked to compare elements `cmp!(a, b)`
+#[macro_export]
+macro_rules! const_select_adv {
+    ($data:expr, $k:expr, $cmp:path) => {{
+        let mut wrapper = $crate::Wrapper($data);
+        let mut data = wrapper.as_mut_slice();
+        let mut k = $k;
+
+        loop {
+            match data.len() {
+                0 | 1 => break,
+                2 => {
+                    if !{$cmp!(data[0], data[1])} {
+                        data.swap(0, 1);
+                    }
+                    break;
+                }
+                _ => {}
+            }
+
+            // Median-of-three pivot, as `const_quicksort_adv!` uses for slices of up to 128
+            // elements; the ninther it uses for larger slices is not used here.
+            let len = data.len();
+            let last = len - 1;
+            let mid = len / 2;
+            let pivot_idx = $crate::const_median3_idx!(data, 0, mid, last, $cmp);
+            data.swap(0, pivot_idx);
+
+            // A 3-way (Dutch-flag) partition around `data[0]`, rather than a plain 2-way Hoare
+            // partition: every element equal to the pivot lands in one contiguous region in a
+            // single pass, so duplicate-heavy data (ties, default/enum-like keys) collapses
+            // immediately instead of being re-split pivot-by-pivot, which is what would otherwise
+            // make selecting a median over mostly-equal data quadratic.
+            let mut lt = 1;
+            let mut i = 1;
+            let mut gt = last;
+            while i <= gt {
+                if {$cmp!(data[i], data[0])} {
+                    data.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                } else if {$cmp!(data[0], data[i])} {
+                    data.swap(i, gt);
+                    gt -= 1;
+                } else {
+                    i += 1;
+                }
+            }
+            data.swap(0, lt - 1);
+            let less_end = lt - 1;
+            let equal_end = i;
+
+            // Only the partition actually containing `k` needs any further work; if `k` falls
+            // inside the equal region, `data[k]` already holds the correct value.
+            if k < less_end {
+                let (lo_part, _) = data.split_at_mut(less_end);
+                data = lo_part;
+            } else if k < equal_end {
+                break;
+            } else {
+                let (_, hi_part) = data.split_at_mut(equal_end);
+                data = hi_part;
+                k -= equal_end;
+            }
+        }
+
+        wrapper.0
+    }};
+}
+
+/// Partitions a `const` array or mutable slice as a `const` expression so that `data[0..=k]`
+/// holds the `k + 1` smallest elements in sorted order — a cheap compile-time "top-k" built from
+/// [`const_select!`] followed by an insertion sort of just that head.
+///
+/// ```
+/// use sort_const::const_partial_sort;
+///
+/// const DATA: &[u8] = &const_partial_sort!([5, 3, 1, 4, 2], 2);
+///
+/// assert_eq!(&DATA[..=2], [1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! const_partial_sort {
+    ($data:expr, $k:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_partial_sort_adv!($data, $k, cmp)
+    }};
+    ($data:expr, $k:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_partial_sort_adv!($data, $k, cmp)
+    }};
+    ($data:expr, $k:expr, $cmp:expr) => {{
+        macro_rules! cmp {
+            ($a:expr, $b:expr) => {{ $cmp(&$a, &$b) }};
+        }
+        $crate::const_partial_sort_adv!($data, $k, cmp)
+    }};
+}
+
+/// Partitions a `const` array or mutable slice as a `const` expression so that `data[0..=k]`
+/// holds the `k + 1` smallest elements in sorted order.
+///
+/// The first parameter is the data, the second is the target index `k`, the third is the path
+/// identifying the macro which should be invoked to compare elements `cmp!(a, b)`
+#[macro_export]
+macro_rules! const_partial_sort_adv {
+    ($data:expr, $k:expr, $cmp:path) => {{
+        let mut wrapped = $crate::Wrapper($crate::const_select_adv!($data, $k, $cmp));
+        {
+            let arr = wrapped.as_mut_slice();
+            let top = $k + 1;
+
+            let mut i = 1;
+            while i < top {
+                let mut j = i;
+                while j > 0 && {$cmp!(arr[j], arr[j - 1])} {
+                    arr.swap(j, j - 1);
+                    j -= 1;
+                }
+                i += 1;
+            }
+        }
+        wrapped.0
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using quicksort, ordering by a
+/// key computed once per element up front rather than re-deriving it on every comparison.
+///
+/// `|x| key_expr` is evaluated once for each element into a parallel key buffer; the same index
+/// permutation the sort applies to that key buffer is mirrored onto `data` at every swap, so the
+/// elements themselves end up in the order their (materialized-once) keys would sort to. Keys
+/// are compared with `<` by default, or with an optional `|a, b| cmp_expr` key comparator. Keys
+/// are partitioned using the same hardened introsort as [`const_quicksort!`] (median-of-three/
+/// ninther pivot, insertion-sort base case, heapsort fallback once the depth budget runs out), so
+/// duplicate-heavy keys don't trigger quadratic behavior.
+///
+/// The required `@` prefix parameter is the capacity of the key buffer (and thus of the partition
+/// index stack); it must be at least as large as the data. Unlike `const_quicksort!`'s `@depth`,
+/// the key buffer holds one entry per element, so there is no safe size-independent default.
+///
+/// ```
+/// use sort_const::const_quicksort_by_key;
+///
+/// const DATA: &[(u8, u8)] = &const_quicksort_by_key!(@3, [(3, 0), (1, 0), (2, 0)], |x| x.0);
+///
+/// assert_eq!(DATA, [(1, 0), (2, 0), (3, 0)]);
+/// ```
+///
+/// ```
+/// use sort_const::const_quicksort_by_key;
+///
+/// const DATA: &[(u8, u8)] = &const_quicksort_by_key!(@3, [(3, 0), (1, 0), (2, 0)], |x| x.0, |a, b| *a > *b);
+///
+/// assert_eq!(DATA, [(3, 0), (2, 0), (1, 0)]);
+/// ```
+#[macro_export]
+macro_rules! const_quicksort_by_key {
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_quicksort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_quicksort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using introsort, ordering by a
+/// precomputed key. Carries the same median-of-three/ninther pivot, insertion-sort base case, and
+/// depth-budget-with-heapsort-fallback as [`const_quicksort_adv!`], so duplicate-heavy keys (ties,
+/// default/enum-like values) can't trigger the O(n²)/unbounded-recursion behavior a bare
+/// median-of-three quicksort would.
+///
+/// The required `@` prefix parameter is the capacity of the key buffer (and thus of the partition
+/// index stack, which is bounded the same way); it must be at least as large as the data.
+///
+/// The first parameter is the data to be sorted, the second is the path identifying the macro
+/// used to compute each element's key `key!(element)`, the third is the path identifying the
+/// macro used to compare two keys `cmp!(a, b)`.
+#[macro_export]
+macro_rules! const_quicksort_by_key_adv {
+    (@$cap:literal, $data:expr, $key:path, $cmp:path) => {{
+        const INSERTION_SORT_THRESHOLD: usize = 16;
+
+        let mut data = $crate::Wrapper($data);
+        let arr = data.as_mut_slice();
+        let len = arr.len();
+
+        let mut key_storage = $crate::ArrayVec::<_, $cap>::new();
+        let mut idx = 0;
+        while idx < len {
+            $crate::expect_push(&mut key_storage, {$key!(arr[idx])});
+            idx += 1;
+        }
+        let keys = key_storage.as_mut_slice();
+
+        // Stack of `(lo, hi, budget)`: `[lo, hi)` index ranges still to be partitioned along with
+        // their remaining partition-depth budget, mirroring `const_quicksort_adv!`'s slice-and-
+        // budget stack but as index triples so `keys` and `arr` stay in lockstep without
+        // re-slicing at every partition site.
+        let root_budget = 2 * $crate::floor_log2(len) as usize;
+        let mut ranges = $crate::ArrayVec::<(usize, usize, usize), $cap>::new();
+        if len > 1 {
+            $crate::expect_push(&mut ranges, (0, len, root_budget));
+        }
+        while let Some((lo, hi, budget)) = ranges.pop() {
+            let range_len = hi - lo;
+            match range_len {
+                0 | 1 => continue,
+                2 => {
+                    if !{$cmp!(keys[lo], keys[lo + 1])} {
+                        keys.swap(lo, lo + 1);
+                        arr.swap(lo, lo + 1);
+                    }
+                    continue;
+                }
+                n if n <= INSERTION_SORT_THRESHOLD => {
+                    let mut i = lo + 1;
+                    while i < hi {
+                        let mut j = i;
+                        while j > lo && {$cmp!(keys[j], keys[j - 1])} {
+                            keys.swap(j, j - 1);
+                            arr.swap(j, j - 1);
+                            j -= 1;
+                        }
+                        i += 1;
+                    }
+                    continue;
+                }
+                n if budget == 0 => {
+                    // Depth budget exhausted: fall back to heapsort on this sub-range (keeping
+                    // `arr` in lockstep via `const_heap_sift_down_by_key!`) to guarantee O(n log n)
+                    // regardless of pivot choices from here on.
+                    let (_, keys_tail) = keys.split_at_mut(lo);
+                    let (sub_keys, _) = keys_tail.split_at_mut(n);
+                    let (_, arr_tail) = arr.split_at_mut(lo);
+                    let (sub_arr, _) = arr_tail.split_at_mut(n);
+
+                    let mut i = n / 2;
+                    while i > 0 {
+                        i -= 1;
+                        $crate::const_heap_sift_down_by_key!(sub_keys, sub_arr, i, n, $cmp);
+                    }
+                    let mut end = n;
+                    while end > 1 {
+                        end -= 1;
+                        sub_keys.swap(0, end);
+                        sub_arr.swap(0, end);
+                        $crate::const_heap_sift_down_by_key!(sub_keys, sub_arr, 0, end, $cmp);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let last = hi - 1;
+            let mid = lo + range_len / 2;
+            let pivot_idx = if range_len > 128 {
+                // A ninther, as in `const_quicksort_adv!`, to resist the patterns that defeat a
+                // plain median-of-three on larger inputs.
+                let third = range_len / 8;
+                let p_lo = $crate::const_median3_idx!(keys, lo, lo + third, lo + 2 * third, $cmp);
+                let p_mi = $crate::const_median3_idx!(keys, mid - third, mid, mid + third, $cmp);
+                let p_hi = $crate::const_median3_idx!(keys, last - 2 * third, last - third, last, $cmp);
+                $crate::const_median3_idx!(keys, p_lo, p_mi, p_hi, $cmp)
+            } else {
+                $crate::const_median3_idx!(keys, lo, mid, last, $cmp)
+            };
+            keys.swap(lo, pivot_idx);
+            arr.swap(lo, pivot_idx);
+
+            let mut left = lo + 1;
+            let mut right = hi - 1;
+            while left <= right {
+                if {$cmp!(keys[left], keys[lo])} {
+                    left += 1;
+                } else if !{$cmp!(keys[right], keys[lo])} {
+                    if right == lo + 1 {
+                        break;
+                    }
+                    right -= 1;
+                } else {
+                    keys.swap(left, right);
+                    arr.swap(left, right);
+                    left += 1;
+                    if right == lo + 1 {
+                        break;
+                    }
+                    right -= 1;
+                }
+            }
+            left -= 1;
+            keys.swap(lo, left);
+            arr.swap(lo, left);
+
+            let child_budget = budget - 1;
+            if left - lo >= hi - (left + 1) {
+                $crate::expect_push(&mut ranges, (lo, left, child_budget));
+                $crate::expect_push(&mut ranges, (left + 1, hi, child_budget));
+            } else {
+                $crate::expect_push(&mut ranges, (left + 1, hi, child_budget));
+                $crate::expect_push(&mut ranges, (lo, left, child_budget));
+            }
+        }
+        ::core::mem::forget(ranges);
+        ::core::mem::forget(key_storage);
+
+        data.0
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using shellsort, ordering by a
+/// key computed once per element up front. See [`const_quicksort_by_key!`] for the calling
+/// convention.
+///
+/// The gap sequence defaults to [`A366726`] if omitted, but the capacity of the key buffer (which
+/// must be at least as large as the data) has no safe size-independent default and is required.
+///
+/// ```
+/// use sort_const::const_shellsort_by_key;
+///
+/// const DATA: &[(u8, u8)] = &const_shellsort_by_key!(@3, [(3, 0), (1, 0), (2, 0)], |x| x.0);
+///
+/// assert_eq!(DATA, [(1, 0), (2, 0), (3, 0)]);
+/// ```
+#[macro_export]
+macro_rules! const_shellsort_by_key {
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_shellsort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+    (@$seq:expr, @$cap:literal, $data:expr, |$x:ident| $key:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_shellsort_by_key_adv!(@$seq, @$cap, $data, keyfn, keycmp)
+    }};
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_shellsort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+    (@$seq:expr, @$cap:literal, $data:expr, |$x:ident| $key:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_shellsort_by_key_adv!(@$seq, @$cap, $data, keyfn, keycmp)
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using shellsort, ordering by a
+/// precomputed key.
+///
+/// The `@` prefix parameters are the gap sequence (defaulting to [`A366726`] if omitted) and the
+/// capacity of the key buffer, in that order; the capacity must be at least as large as the data
+/// and has no default.
+///
+/// The first parameter is the data to be sorted, the second is the path identifying the macro
+/// used to compute each element's key `key!(element)`, the third is the path identifying the
+/// macro used to compare two keys `cmp!(a, b)`.
+#[macro_export]
+macro_rules! const_shellsort_by_key_adv {
+    (@$cap:literal, $data:expr, $key:path, $cmp:path) => { $crate::const_shellsort_by_key_adv!(@$crate::A366726, @$cap, $data, $key, $cmp) };
+    (@$seq:expr, @$cap:literal, $data:expr, $key:path, $cmp:path) => {{
+        const GAPS: &[usize] = &$seq;
+        const GAPS_LEN: usize = GAPS.len();
+
+        let mut data = $crate::Wrapper($data);
+        let arr = data.as_mut_slice();
+        let n = arr.len();
+
+        let mut key_storage = $crate::ArrayVec::<_, $cap>::new();
+        let mut idx = 0;
+        while idx < n {
+            $crate::expect_push(&mut key_storage, {$key!(arr[idx])});
+            idx += 1;
+        }
+        let keys = key_storage.as_mut_slice();
+
+        let mut gaps = {
+            let mut gaps = $crate::ArrayVec::<usize, GAPS_LEN>::new();
+            let mut i = 0;
+            while i < GAPS_LEN && GAPS[i] < n {
+                $crate::expect_push(&mut gaps, GAPS[i]);
+                i += 1;
+            }
+            gaps
+        };
+
+        while let Some(gap) = gaps.pop() {
+            let mut i = gap;
+            while i < n {
+                // Move both the key and its element through the same hole in lockstep.
+                let temp_key = unsafe { ::core::ptr::read(&keys[i]) };
+                let temp_data = unsafe { ::core::ptr::read(&arr[i]) };
+
+                let mut j = i;
+                while j >= gap && {$cmp!(temp_key, keys[j - gap])} {
+                    unsafe {
+                        ::core::ptr::copy(&keys[j - gap], &mut keys[j], 1);
+                        ::core::ptr::copy(&arr[j - gap], &mut arr[j], 1);
+                    }
+                    j -= gap;
+                }
+
+                unsafe {
+                    ::core::ptr::write(&mut keys[j], temp_key);
+                    ::core::ptr::write(&mut arr[j], temp_data);
+                }
+                i += 1;
+            }
+        }
+        ::core::mem::forget(gaps);
+        ::core::mem::forget(key_storage);
+
+        data.0
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using merge sort, ordering by a
+/// key computed once per element up front. See [`const_quicksort_by_key!`] for the calling
+/// convention; like [`const_mergesort!`], this sort is stable.
+///
+/// The required `@` prefix parameter is the capacity of the key buffer and merge scratch buffers;
+/// it must be at least as large as the data being sorted and has no safe size-independent default.
+///
+/// ```
+/// use sort_const::const_mergesort_by_key;
+///
+/// const DATA: &[(u8, u8)] = &const_mergesort_by_key!(@3, [(3, 0), (1, 0), (2, 0)], |x| x.0);
+///
+/// assert_eq!(DATA, [(1, 0), (2, 0), (3, 0)]);
+/// ```
+#[macro_export]
+macro_rules! const_mergesort_by_key {
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a:expr, $b:expr) => {{ $a < $b }};
+        }
+        $crate::const_mergesort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+    (@$cap:literal, $data:expr, |$x:ident| $key:expr, |$a:ident, $b:ident| $cmp:expr) => {{
+        macro_rules! keyfn {
+            ($x_:expr) => {{ let $x = &$x_; $key }};
+        }
+        macro_rules! keycmp {
+            ($a_:expr, $b_:expr) => {{
+                let ($a, $b) = (&$a_, &$b_);
+                $cmp
+            }};
+        }
+        $crate::const_mergesort_by_key_adv!(@$cap, $data, keyfn, keycmp)
+    }};
+}
+
+/// Sorts a `const` array or mutable slice as a `const` expression using merge sort, ordering by a
+/// precomputed key.
+///
+/// The required `@` prefix parameter is the capacity of the key buffer and merge scratch buffers;
+/// it must be at least as large as the data being sorted.
+///
+/// The first parameter is the data to be sorted, the second is the path identifying the macro
+/// used to compute each element's key `key!(element)`, the third is the path identifying the
+/// macro used to compare two keys `cmp!(a, b)`.
+#[macro_export]
+macro_rules! const_mergesort_by_key_adv {
+    (@$cap:literal, $data:expr, $key:path, $cmp:path) => {{
+        let mut data = $crate::Wrapper($data);
+        let arr = data.as_mut_slice();
+        let n = arr.len();
+
+        let mut key_storage = $crate::ArrayVec::<_, $cap>::new();
+        let mut idx = 0;
+        while idx < n {
+            $crate::expect_push(&mut key_storage, {$key!(arr[idx])});
+            idx += 1;
+        }
+        let keys = key_storage.as_mut_slice();
+
+        let mut width = 1;
+        while width < n {
+            let mut lo = 0;
+            while lo < n {
+                let mid = if lo + width < n { lo + width } else { n };
+                let hi = if lo + 2 * width < n { lo + 2 * width } else { n };
+
+                if mid < hi {
+                    let mut merged_keys = $crate::ArrayVec::<_, $cap>::new();
+                    let mut merged_data = $crate::ArrayVec::<_, $cap>::new();
+                    let mut i = lo;
+                    let mut j = mid;
+                    while i < mid && j < hi {
+                        if !{$cmp!(keys[j], keys[i])} {
+                            $crate::expect_push(&mut merged_keys, unsafe { ::core::ptr::read(&keys[i]) });
+                            $crate::expect_push(&mut merged_data, unsafe { ::core::ptr::read(&arr[i]) });
+                            i += 1;
+                        } else {
+                            $crate::expect_push(&mut merged_keys, unsafe { ::core::ptr::read(&keys[j]) });
+                            $crate::expect_push(&mut merged_data, unsafe { ::core::ptr::read(&arr[j]) });
+                            j += 1;
+                        }
+                    }
+                    while i < mid {
+                        $crate::expect_push(&mut merged_keys, unsafe { ::core::ptr::read(&keys[i]) });
+                        $crate::expect_push(&mut merged_data, unsafe { ::core::ptr::read(&arr[i]) });
+                        i += 1;
+                    }
+                    while j < hi {
+                        $crate::expect_push(&mut merged_keys, unsafe { ::core::ptr::read(&keys[j]) });
+                        $crate::expect_push(&mut merged_data, unsafe { ::core::ptr::read(&arr[j]) });
+                        j += 1;
+                    }
+
+                    let mut k = hi;
+                    while let Some(value) = merged_data.pop() {
+                        let key = merged_keys.pop().expect("merged_data and merged_keys stay in lockstep");
+                        k -= 1;
+                        unsafe {
+                            ::core::ptr::write(&mut arr[k], value);
+                            ::core::ptr::write(&mut keys[k], key);
+                        }
+                    }
+                    ::core::mem::forget(merged_data);
+                    ::core::mem::forget(merged_keys);
+                }
+
+                lo += 2 * width;
+            }
+            width *= 2;
+        }
+
+        ::core::mem::forget(key_storage);
+        data.0
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    #[derive(Debug)]
+    struct Foo(u8);
+
+    const fn lt(a: &u8, b: &u8) -> bool {
+        *a < *b
+    }
+
+    const U8S: &[u8] = &const_quicksort!([3, 2, 1]);
+    const U8S_FN: &[u8] = &const_quicksort!([3, 2, 1], lt);
+    const FOOS: &[Foo] = &const_quicksort!([Foo(1), Foo(2), Foo(4), Foo(3)], |a, b| a.0 > b.0);
+    const FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+        const_quicksort!(foo.split_last_mut().expect("failed").1, |a, b| a.0 > b.0);
+        foo
+    };
+
+    #[test]
+    fn test_u8() {
+        assert_eq!(U8S, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_u8_fn() {
+        assert_eq!(U8S_FN, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_foo() {
+        assert!(FOOS.iter().map(|v| v.0).eq([4, 3, 2, 1]));
+        assert!(FOOS_MUT_REF.iter().map(|v| v.0).eq([4, 2, 1, 3]));
+    }
+
+    const HEAPSORT_FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+        const_heapsort!(foo.split_last_mut().expect("failed").1, |a, b| a.0 > b.0);
+        foo
+    };
+
+    #[test]
+    fn test_heapsort_foo() {
+        assert!(HEAPSORT_FOOS_MUT_REF.iter().map(|v| v.0).eq([4, 2, 1, 3]));
+    }
+
+    const SELECT_FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(5), Foo(3), Foo(1), Foo(4), Foo(2)];
+        const_select!(foo.split_last_mut().expect("failed").1, 1, |a, b| a.0 > b.0);
+        foo
+    };
+
+    #[test]
+    fn test_select_foo() {
+        assert_eq!(SELECT_FOOS_MUT_REF[1].0, 4);
+    }
+
+    const PARTIAL_SORT_FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(5), Foo(3), Foo(1), Foo(4), Foo(2)];
+        const_partial_sort!(foo.split_last_mut().expect("failed").1, 1, |a, b| a.0 > b.0);
+        foo
+    };
+
+    #[test]
+    fn test_partial_sort_foo() {
+        assert!(PARTIAL_SORT_FOOS_MUT_REF[..2].iter().map(|v| v.0).eq([5, 4]));
+    }
+
+    const QUICKSORT_BY_KEY_FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(1), Foo(2), Foo(4), Foo(3)];
+        const_quicksort_by_key!(@4, foo.split_last_mut().expect("failed").1, |x| x.0, |a, b| *a > *b);
+        foo
+    };
+
+    #[test]
+    fn test_quicksort_by_key_foo() {
+        assert!(QUICKSORT_BY_KEY_FOOS_MUT_REF.iter().map(|v| v.0).eq([4, 2, 1, 3]));
+    }
+
+    const SHELLSORT_BY_KEY_FOOS_MUT_REF: &[Foo] = &{
+        let mut foo = [Foo(4), Foo(1), Foo(2), Foo(3)];
+        const_shellsort_by_key!(@4, foo.split_last_mut().expect("failed").1, |x| x.0);
+        foo
+    };
+
+    #[test]
+    fn test_shellsort_by_key_foo() {
+        assert!(SHELLSORT_BY_KEY_FOOS_MUT_REF.iter().map(|v| v.0).eq([1, 2, 4, 3]));
+    }
+
+    const MERGESORT_BY_KEY_PAIRS_MUT_REF: &[(u8, u8)] = &{
+        let mut pairs = [(1, 0), (0, 0), (1, 1), (0, 1), (9, 9)];
+        const_mergesort_by_key!(
+            @5,
+            pairs.split_last_mut().expect("failed").1,
+            |x| x.0
+        );
+        pairs
+    };
+
+    #[test]
+    fn test_mergesort_by_key_stable() {
+        // Elements with equal keys must keep their original relative order.
+        assert_eq!(
+            &MERGESORT_BY_KEY_PAIRS_MUT_REF[..4],
+            [(0, 0), (0, 1), (1, 0), (1, 1)]
+        );
+    }
+
+    const MERGESORT_PAIRS_MUT_REF: &[(u8, u8)] = &{
+        let mut pairs = [(1, 0), (0, 0), (1, 1), (0, 1), (9, 9)];
+        const_mergesort!(
+            @5,
+            pairs.split_last_mut().expect("failed").1,
+            |a, b| a.0 < b.0
+        );
+        pairs
+    };
+
+    #[test]
+    fn test_mergesort_stable() {
+        // Elements that compare equal (same `.0`) must keep their original relative order.
+        assert_eq!(
+            &MERGESORT_PAIRS_MUT_REF[..4],
+            [(0, 0), (0, 1), (1, 0), (1, 1)]
+        );
     }
 }